@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
 use crate::field::extension_field::target::ExtensionTarget;
 use crate::field::extension_field::Extendable;
 use crate::field::field_types::{Field, RichField};
@@ -5,31 +9,134 @@ use crate::plonk::circuit_builder::CircuitBuilder;
 
 /// Compute partial products of the original vector `v` such that all products consist of `max_degree`
 /// or less elements. This is done until we've computed the product `P` of all elements in the vector.
+/// If `v.len()` isn't a multiple of `max_degree`, one final entry is emitted for the short remainder
+/// chunk, so the last entry always equals the product of all of `v`.
 pub fn partial_products<F: Field>(v: &[F], max_degree: usize) -> Vec<F> {
     debug_assert!(max_degree > 1);
     let mut res = Vec::new();
     let mut acc = F::ONE;
     let chunk_size = max_degree;
-    for chunk in v.chunks_exact(chunk_size) {
+    let chunks = v.chunks_exact(chunk_size);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
         acc *= chunk.iter().copied().product();
         res.push(acc);
     }
+    if !remainder.is_empty() {
+        acc *= remainder.iter().copied().product();
+        res.push(acc);
+    }
 
     res
 }
 
-/// Returns a tuple `(a,b)`, where `a` is the length of the output of `partial_products()` on a
-/// vector of length `n`, and `b` is the number of original elements consumed in `partial_products()`.
-pub fn num_partial_products(n: usize, max_degree: usize) -> (usize, usize) {
+/// Multiplies the elements of `chunk` via a balanced tree reduction (rather than a sequential
+/// `fold`), splitting work across rayon's thread pool. Used by `partial_products_parallel` so
+/// that even a single wide chunk's product is computed in parallel.
+fn tree_product<F: Field + Send + Sync>(chunk: &[F]) -> F {
+    match chunk.len() {
+        0 => F::ONE,
+        1 => chunk[0],
+        _ => {
+            let mid = chunk.len() / 2;
+            let (left, right) = chunk.split_at(mid);
+            let (l, r) = rayon::join(|| tree_product(left), || tree_product(right));
+            l * r
+        }
+    }
+}
+
+/// Parallel counterpart to `partial_products`, for evaluating partial products row-by-row across
+/// a large low-degree-extension domain, where the per-row cost dominates proving time. Each
+/// chunk's product is computed via a balanced `tree_product` reduction instead of a sequential
+/// `fold`, chunks are themselves processed concurrently with rayon, and the result is returned as
+/// an `Arc<[F]>` so the prover can share the partial-product columns across worker threads (e.g.
+/// the FRI/commitment stages) without cloning. Prefer the plain `partial_products` for small
+/// inputs and tests, where the parallelism overhead isn't worth it. Mirrors `partial_products`'s
+/// handling of a trailing short chunk.
+pub fn partial_products_parallel<F: Field + Send + Sync>(v: &[F], max_degree: usize) -> Arc<[F]> {
+    debug_assert!(max_degree > 1);
+    let chunk_size = max_degree;
+    let chunks = v.par_chunks_exact(chunk_size);
+    let remainder = chunks.remainder();
+    let mut chunk_products: Vec<F> = chunks.map(tree_product).collect();
+    if !remainder.is_empty() {
+        chunk_products.push(tree_product(remainder));
+    }
+
+    let mut acc = F::ONE;
+    let res: Vec<F> = chunk_products
+        .into_iter()
+        .map(|p| {
+            acc *= p;
+            acc
+        })
+        .collect();
+
+    Arc::from(res)
+}
+
+/// Returns a tuple `(a,b)`, where `a` is the (padded) length of the output of `partial_products()`
+/// on a vector of length `n`, including the extra entry for a short trailing chunk if one exists,
+/// and `b` indicates whether such a trailing chunk exists, i.e. whether `n` isn't a multiple of
+/// `max_degree`.
+pub fn num_partial_products(n: usize, max_degree: usize) -> (usize, bool) {
     debug_assert!(max_degree > 1);
     let chunk_size = max_degree;
-    let num_chunks = n / chunk_size;
+    let has_tail = n % chunk_size != 0;
+
+    (n / chunk_size + has_tail as usize, has_tail)
+}
 
-    (num_chunks, num_chunks * chunk_size)
+/// The result of planning how a grand product over `num_columns` columns is split across
+/// committed partial-product polynomials, given a constraint system whose quotient has degree
+/// `quotient_degree`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PartialProductsLayout {
+    /// The maximum number of original elements any single chunk (and hence any single
+    /// coherence constraint) may combine, i.e. `quotient_degree - 1`.
+    pub max_degree: usize,
+    /// The number of intermediate partial-product polynomials the prover must commit to.
+    pub num_partial_products: usize,
+    /// The index (into the `num_columns` original elements) at which each chunk ends, in order.
+    /// Has `num_partial_products` entries, the last of which always equals `num_columns`.
+    pub chunk_boundaries: Vec<usize>,
+    /// The number of coherence constraints `check_partial_products` will emit to link the
+    /// intermediate partial-product polynomials together.
+    pub num_intermediate_partial_product_relations: usize,
+}
+
+/// Plans how to split a single `Z`-style running product over `num_columns` columns into several
+/// committed partial-product polynomials, given the constraint system's quotient degree
+/// `quotient_degree`. A running product spanning all columns would need degree `num_columns`,
+/// which generally exceeds `quotient_degree`, so we pick the largest `max_degree = quotient_degree
+/// - 1` for which a single coherence constraint (degree `max_degree + 1`) still fits, then reuse
+/// `num_partial_products`'s chunking to lay out the rest. This lets `CircuitBuilder` size its
+/// witness and commitment phases without callers hand-picking `max_degree` or slicing the tail.
+pub fn partial_products_layout(num_columns: usize, quotient_degree: usize) -> PartialProductsLayout {
+    debug_assert!(
+        quotient_degree > 2,
+        "quotient degree must leave room for the partial-product step constraint"
+    );
+    let max_degree = quotient_degree - 1;
+    let (num_partial_products, has_tail) = num_partial_products(num_columns, max_degree);
+    let num_full_chunks = num_partial_products - has_tail as usize;
+    let mut chunk_boundaries: Vec<usize> = (1..=num_full_chunks).map(|i| i * max_degree).collect();
+    if has_tail {
+        chunk_boundaries.push(num_columns);
+    }
+
+    PartialProductsLayout {
+        max_degree,
+        num_partial_products,
+        chunk_boundaries,
+        num_intermediate_partial_product_relations: num_partial_products,
+    }
 }
 
 /// Checks that the partial products of `numerators/denominators` are coherent with those in `partials` by only computing
-/// products of size `max_degree` or less.
+/// products of size `max_degree` or less. If the inputs' length isn't a multiple of `max_degree`, a
+/// final coherence constraint is emitted for the short trailing chunk.
 pub fn check_partial_products<F: Field>(
     numerators: &[F],
     denominators: &[F],
@@ -41,15 +148,21 @@ pub fn check_partial_products<F: Field>(
     let mut partials = partials.iter();
     let mut res = Vec::new();
     let chunk_size = max_degree;
-    for (nume_chunk, deno_chunk) in numerators
-        .chunks_exact(chunk_size)
-        .zip(denominators.chunks_exact(chunk_size))
-    {
+    let nume_chunks = numerators.chunks_exact(chunk_size);
+    let deno_chunks = denominators.chunks_exact(chunk_size);
+    let nume_remainder = nume_chunks.remainder();
+    let deno_remainder = deno_chunks.remainder();
+    for (nume_chunk, deno_chunk) in nume_chunks.zip(deno_chunks) {
         acc *= nume_chunk.iter().copied().product();
-        let mut new_acc = *partials.next().unwrap();
+        let new_acc = *partials.next().unwrap();
         res.push(acc - new_acc * deno_chunk.iter().copied().product());
         acc = new_acc;
     }
+    if !nume_remainder.is_empty() {
+        acc *= nume_remainder.iter().copied().product();
+        let new_acc = *partials.next().unwrap();
+        res.push(acc - new_acc * deno_remainder.iter().copied().product());
+    }
     debug_assert!(partials.next().is_none());
 
     res
@@ -67,10 +180,11 @@ pub fn check_partial_products_recursively<F: RichField + Extendable<D>, const D:
     let mut partials = partials.iter();
     let mut res = Vec::new();
     let chunk_size = max_degree;
-    for (nume_chunk, deno_chunk) in numerators
-        .chunks_exact(chunk_size)
-        .zip(denominators.chunks_exact(chunk_size))
-    {
+    let nume_chunks = numerators.chunks_exact(chunk_size);
+    let deno_chunks = denominators.chunks_exact(chunk_size);
+    let nume_remainder = nume_chunks.remainder();
+    let deno_remainder = deno_chunks.remainder();
+    for (nume_chunk, deno_chunk) in nume_chunks.zip(deno_chunks) {
         let nume_product = builder.mul_many_extension(nume_chunk);
         let deno_product = builder.mul_many_extension(deno_chunk);
         let new_acc = *partials.next().unwrap();
@@ -79,15 +193,194 @@ pub fn check_partial_products_recursively<F: RichField + Extendable<D>, const D:
         res.push(builder.mul_sub_extension(acc, nume_product, new_acc_deno));
         acc = new_acc;
     }
+    if !nume_remainder.is_empty() {
+        let nume_product = builder.mul_many_extension(nume_remainder);
+        let deno_product = builder.mul_many_extension(deno_remainder);
+        let new_acc = *partials.next().unwrap();
+        let new_acc_deno = builder.mul_extension(new_acc, deno_product);
+        res.push(builder.mul_sub_extension(acc, nume_product, new_acc_deno));
+    }
     debug_assert!(partials.next().is_none());
 
     res
 }
 
+/// Collapses a chunk of up to `max_degree` denominators `d_0..d_{k-1}` into a single rational term
+/// `N/Delta`, where `Delta = prod_i d_i` and `N = sum_i prod_{j != i} d_j`, i.e. `N/Delta = sum_i 1/d_i`.
+/// Accumulating this across chunks lets us compute a running sum of reciprocals `S = sum_i 1/d_i`
+/// without ever inverting a field element more than once per chunk, and while keeping the per-chunk
+/// relation used by `check_partial_sums` bounded to degree `max_degree + 1`.
+///
+/// This is the logarithmic-derivative analogue of `partial_products`: proving `f_i \in {t_j}` with
+/// multiplicities `m_j` reduces to checking `sum_i 1/(alpha - f_i) = sum_j m_j/(alpha - t_j)` at a
+/// random challenge `alpha`, so callers pass `alpha - f_i` (or `alpha - t_j`) as the denominators.
+///
+/// Mirrors `partial_products`'s handling of a trailing short chunk: if `denominators.len()` isn't
+/// a multiple of `max_degree`, one final entry is emitted for the remainder, so that the length of
+/// the output always matches `num_partial_products` (and the last entry is always the full sum).
+pub fn partial_sums<F: Field>(denominators: &[F], max_degree: usize) -> Vec<F> {
+    debug_assert!(max_degree > 1);
+    let mut res = Vec::new();
+    let mut acc = F::ZERO;
+    let chunk_size = max_degree;
+    let chunks = denominators.chunks_exact(chunk_size);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        acc += chunk_reciprocal_sum(chunk);
+        res.push(acc);
+    }
+    if !remainder.is_empty() {
+        acc += chunk_reciprocal_sum(remainder);
+        res.push(acc);
+    }
+
+    res
+}
+
+/// `sum_i 1/d_i` for a single chunk, computed as `N/Delta` per `partial_sums`'s doc comment.
+fn chunk_reciprocal_sum<F: Field>(chunk: &[F]) -> F {
+    let delta: F = chunk.iter().copied().product();
+    let numerator: F = (0..chunk.len())
+        .map(|i| {
+            chunk
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &d)| d)
+                .product::<F>()
+        })
+        .sum();
+    numerator / delta
+}
+
+/// Checks that the partial sums of `numerators/denominators` (i.e. `sum_i numerators_i/denominators_i`)
+/// are coherent with those in `partials`, by only computing, per chunk of up to `max_degree` terms, the
+/// degree `max_degree + 1` relation `(S_next - S_cur) * Delta - N = 0`, where `Delta = prod_i d_i` and
+/// `N = sum_i numerators_i * prod_{j != i} d_j`. This mirrors `check_partial_products`, but enforces the
+/// additive (lookup) relation instead of the multiplicative (grand-product) one, and never requires
+/// inverting a field element in-circuit. If the inputs' length isn't a multiple of `max_degree`, a
+/// final coherence constraint is emitted for the short trailing chunk, mirroring
+/// `check_partial_products`.
+pub fn check_partial_sums<F: Field>(
+    numerators: &[F],
+    denominators: &[F],
+    partials: &[F],
+    mut acc: F,
+    max_degree: usize,
+) -> Vec<F> {
+    debug_assert!(max_degree > 1);
+    let mut partials = partials.iter();
+    let mut res = Vec::new();
+    let chunk_size = max_degree;
+    let nume_chunks = numerators.chunks_exact(chunk_size);
+    let deno_chunks = denominators.chunks_exact(chunk_size);
+    let nume_remainder = nume_chunks.remainder();
+    let deno_remainder = deno_chunks.remainder();
+    for (nume_chunk, deno_chunk) in nume_chunks.zip(deno_chunks) {
+        let new_acc = *partials.next().unwrap();
+        res.push(check_sums_chunk_residual(nume_chunk, deno_chunk, acc, new_acc));
+        acc = new_acc;
+    }
+    if !nume_remainder.is_empty() {
+        let new_acc = *partials.next().unwrap();
+        res.push(check_sums_chunk_residual(
+            nume_remainder,
+            deno_remainder,
+            acc,
+            new_acc,
+        ));
+    }
+    debug_assert!(partials.next().is_none());
+
+    res
+}
+
+/// The degree `max_degree + 1` residual `(new_acc - acc) * Delta - N` for a single chunk, where
+/// `Delta = prod_i d_i` and `N = sum_i numerators_i * prod_{j != i} d_j`.
+fn check_sums_chunk_residual<F: Field>(nume_chunk: &[F], deno_chunk: &[F], acc: F, new_acc: F) -> F {
+    let delta: F = deno_chunk.iter().copied().product();
+    let numerator: F = (0..deno_chunk.len())
+        .map(|i| {
+            nume_chunk[i]
+                * deno_chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &d)| d)
+                    .product::<F>()
+        })
+        .sum();
+    (new_acc - acc) * delta - numerator
+}
+
+pub fn check_partial_sums_recursively<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    numerators: &[ExtensionTarget<D>],
+    denominators: &[ExtensionTarget<D>],
+    partials: &[ExtensionTarget<D>],
+    mut acc: ExtensionTarget<D>,
+    max_degree: usize,
+) -> Vec<ExtensionTarget<D>> {
+    debug_assert!(max_degree > 1);
+    let mut partials = partials.iter();
+    let mut res = Vec::new();
+    let chunk_size = max_degree;
+    let nume_chunks = numerators.chunks_exact(chunk_size);
+    let deno_chunks = denominators.chunks_exact(chunk_size);
+    let nume_remainder = nume_chunks.remainder();
+    let deno_remainder = deno_chunks.remainder();
+    for (nume_chunk, deno_chunk) in nume_chunks.zip(deno_chunks) {
+        let new_acc = *partials.next().unwrap();
+        res.push(check_sums_chunk_residual_recursively(
+            builder, nume_chunk, deno_chunk, acc, new_acc,
+        ));
+        acc = new_acc;
+    }
+    if !nume_remainder.is_empty() {
+        let new_acc = *partials.next().unwrap();
+        res.push(check_sums_chunk_residual_recursively(
+            builder,
+            nume_remainder,
+            deno_remainder,
+            acc,
+            new_acc,
+        ));
+    }
+    debug_assert!(partials.next().is_none());
+
+    res
+}
+
+/// In-circuit counterpart to `check_sums_chunk_residual`.
+fn check_sums_chunk_residual_recursively<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    nume_chunk: &[ExtensionTarget<D>],
+    deno_chunk: &[ExtensionTarget<D>],
+    acc: ExtensionTarget<D>,
+    new_acc: ExtensionTarget<D>,
+) -> ExtensionTarget<D> {
+    let delta = builder.mul_many_extension(deno_chunk);
+    let mut numerator = builder.zero_extension();
+    for i in 0..deno_chunk.len() {
+        let mut others = Vec::with_capacity(deno_chunk.len() - 1);
+        others.extend(deno_chunk[..i].iter().copied());
+        others.extend(deno_chunk[i + 1..].iter().copied());
+        let others_product = builder.mul_many_extension(&others);
+        let term = builder.mul_extension(nume_chunk[i], others_product);
+        numerator = builder.add_extension(numerator, term);
+    }
+    let diff = builder.sub_extension(new_acc, acc);
+    // Assert that (new_acc - acc) * delta = numerator.
+    builder.mul_sub_extension(diff, delta, numerator)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::field::goldilocks_field::GoldilocksField;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
 
     #[test]
     fn test_partial_products() {
@@ -108,13 +401,11 @@ mod tests {
 
         let nums = num_partial_products(v.len(), 2);
         assert_eq!(p.len(), nums.0);
+        assert!(!nums.1);
         assert!(check_partial_products(&v, &denominators, &p, F::ONE, 2)
             .iter()
             .all(|x| x.is_zero()));
-        assert_eq!(
-            *p.last().unwrap() * v[nums.1..].iter().copied().product::<F>(),
-            v.into_iter().product::<F>(),
-        );
+        assert_eq!(*p.last().unwrap(), v.iter().copied().product::<F>());
 
         let v = [1, 2, 3, 4, 5, 6]
             .into_iter()
@@ -130,12 +421,301 @@ mod tests {
         );
         let nums = num_partial_products(v.len(), 3);
         assert_eq!(p.len(), nums.0);
+        assert!(!nums.1);
         assert!(check_partial_products(&v, &denominators, &p, F::ONE, 3)
             .iter()
             .all(|x| x.is_zero()));
+        assert_eq!(*p.last().unwrap(), v.iter().copied().product::<F>());
+    }
+
+    #[test]
+    fn test_partial_products_ragged() {
+        type F = GoldilocksField;
+        let denominators = vec![F::ONE; 5];
+        let v = [1, 2, 3, 4, 5]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+
+        // 5 elements, max_degree 2: chunks [1,2], [3,4], tail [5].
+        let p = partial_products(&v, 2);
+        assert_eq!(
+            p,
+            [2, 24, 120]
+                .into_iter()
+                .map(|&i| F::from_canonical_u64(i))
+                .collect::<Vec<_>>()
+        );
+        let nums = num_partial_products(v.len(), 2);
+        assert_eq!(p.len(), nums.0);
+        assert!(nums.1);
+        assert!(check_partial_products(&v, &denominators, &p, F::ONE, 2)
+            .iter()
+            .all(|x| x.is_zero()));
+        assert_eq!(*p.last().unwrap(), v.iter().copied().product::<F>());
+        assert_eq!(&*partial_products_parallel(&v, 2), p.as_slice());
+
+        // 5 elements, max_degree 3: chunks [1,2,3], tail [4,5].
+        let p = partial_products(&v, 3);
         assert_eq!(
-            *p.last().unwrap() * v[nums.1..].iter().copied().product::<F>(),
-            v.into_iter().product::<F>(),
+            p,
+            [6, 120]
+                .into_iter()
+                .map(|&i| F::from_canonical_u64(i))
+                .collect::<Vec<_>>()
         );
+        let nums = num_partial_products(v.len(), 3);
+        assert_eq!(p.len(), nums.0);
+        assert!(nums.1);
+        assert!(check_partial_products(&v, &denominators, &p, F::ONE, 3)
+            .iter()
+            .all(|x| x.is_zero()));
+        assert_eq!(*p.last().unwrap(), v.iter().copied().product::<F>());
+        assert_eq!(&*partial_products_parallel(&v, 3), p.as_slice());
+    }
+
+    #[test]
+    fn test_check_partial_products_recursively_ragged() -> anyhow::Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        // 5 elements, max_degree 2: the same ragged shape as `test_partial_products_ragged`.
+        let max_degree = 2;
+        let numerators = [1, 2, 3, 4, 5]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let denominators = vec![F::ONE; 5];
+        let partials = partial_products(&numerators, max_degree);
+
+        let nume_targets = numerators
+            .iter()
+            .map(|&n| builder.constant_extension(n.into()))
+            .collect::<Vec<_>>();
+        let deno_targets = denominators
+            .iter()
+            .map(|&d| builder.constant_extension(d.into()))
+            .collect::<Vec<_>>();
+        let partial_targets = partials
+            .iter()
+            .map(|_| builder.add_virtual_extension_target())
+            .collect::<Vec<_>>();
+        for (&target, &value) in partial_targets.iter().zip(&partials) {
+            pw.set_extension_target(target, value.into());
+        }
+        let acc = builder.one_extension();
+
+        let constraints = check_partial_products_recursively(
+            &mut builder,
+            &nume_targets,
+            &deno_targets,
+            &partial_targets,
+            acc,
+            max_degree,
+        );
+        // One residual per full chunk plus one for the ragged tail, exactly as in the native test.
+        assert_eq!(constraints.len(), partials.len());
+        for constraint in constraints {
+            builder.assert_zero_extension(constraint);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_partial_products_parallel() {
+        type F = GoldilocksField;
+        let v = [1, 2, 3, 4, 5, 6]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        for max_degree in [2, 3] {
+            assert_eq!(
+                &*partial_products_parallel(&v, max_degree),
+                partial_products(&v, max_degree).as_slice(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_partial_products_layout() {
+        // 6 columns, quotient degree 3 => max_degree 2, matching the `partial_products(&v, 2)` case above.
+        let layout = partial_products_layout(6, 3);
+        assert_eq!(layout.max_degree, 2);
+        assert_eq!(layout.num_partial_products, 3);
+        assert_eq!(layout.chunk_boundaries, vec![2, 4, 6]);
+        assert_eq!(layout.num_intermediate_partial_product_relations, 3);
+
+        // 6 columns, quotient degree 4 => max_degree 3, matching the `partial_products(&v, 3)` case above.
+        let layout = partial_products_layout(6, 4);
+        assert_eq!(layout.max_degree, 3);
+        assert_eq!(layout.num_partial_products, 2);
+        assert_eq!(layout.chunk_boundaries, vec![3, 6]);
+        assert_eq!(layout.num_intermediate_partial_product_relations, 2);
+
+        // 5 columns, quotient degree 3 => max_degree 2: chunks [0,2), [2,4), ragged tail [4,5).
+        // `chunk_boundaries` must end at `num_columns`, not at the last full `max_degree` multiple.
+        let layout = partial_products_layout(5, 3);
+        assert_eq!(layout.max_degree, 2);
+        assert_eq!(layout.num_partial_products, 3);
+        assert_eq!(layout.chunk_boundaries, vec![2, 4, 5]);
+        assert_eq!(layout.num_intermediate_partial_product_relations, 3);
+    }
+
+    #[test]
+    fn test_partial_sums() {
+        type F = GoldilocksField;
+        // sum_i 1/d_i for d_i = i + 1, i.e. 1 + 1/2 + ... + 1/6.
+        let denominators = [1, 2, 3, 4, 5, 6]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let numerators = vec![F::ONE; 6];
+        let expected_total: F = denominators.iter().map(|&d| d.inverse()).sum();
+
+        let s = partial_sums(&denominators, 2);
+        assert_eq!(s.len(), 3);
+        assert_eq!(*s.last().unwrap(), expected_total);
+        assert!(
+            check_partial_sums(&numerators, &denominators, &s, F::ZERO, 2)
+                .iter()
+                .all(|x| x.is_zero())
+        );
+
+        let s = partial_sums(&denominators, 3);
+        assert_eq!(s.len(), 2);
+        assert_eq!(*s.last().unwrap(), expected_total);
+        assert!(
+            check_partial_sums(&numerators, &denominators, &s, F::ZERO, 3)
+                .iter()
+                .all(|x| x.is_zero())
+        );
+    }
+
+    #[test]
+    fn test_check_partial_sums_weighted() {
+        type F = GoldilocksField;
+        // Non-uniform numerators stand in for LogUp multiplicities `m_j` in
+        // `sum_j m_j/(alpha - t_j)`; an all-ones test can't catch a `nume_chunk[i]`
+        // transposition/indexing bug since every term would be weighted identically.
+        let numerators = [1, 2, 1, 3, 1, 2]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let denominators = [2, 3, 4, 5, 6, 7]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let expected_total: F = numerators
+            .iter()
+            .zip(&denominators)
+            .map(|(&n, &d)| n / d)
+            .sum();
+
+        for max_degree in [2, 3] {
+            let mut acc = F::ZERO;
+            let partials: Vec<F> = numerators
+                .chunks_exact(max_degree)
+                .zip(denominators.chunks_exact(max_degree))
+                .map(|(nume_chunk, deno_chunk)| {
+                    acc += nume_chunk
+                        .iter()
+                        .zip(deno_chunk)
+                        .map(|(&n, &d)| n / d)
+                        .sum::<F>();
+                    acc
+                })
+                .collect();
+            assert_eq!(*partials.last().unwrap(), expected_total);
+            assert!(
+                check_partial_sums(&numerators, &denominators, &partials, F::ZERO, max_degree)
+                    .iter()
+                    .all(|x| x.is_zero())
+            );
+        }
+    }
+
+    #[test]
+    fn test_partial_sums_ragged() {
+        type F = GoldilocksField;
+        let numerators = vec![F::ONE; 5];
+        let denominators = [1, 2, 3, 4, 5]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let expected_total: F = denominators.iter().map(|&d| d.inverse()).sum();
+
+        for max_degree in [2, 3] {
+            let s = partial_sums(&denominators, max_degree);
+            let nums = num_partial_products(denominators.len(), max_degree);
+            assert_eq!(s.len(), nums.0);
+            assert!(nums.1);
+            assert_eq!(*s.last().unwrap(), expected_total);
+            assert!(
+                check_partial_sums(&numerators, &denominators, &s, F::ZERO, max_degree)
+                    .iter()
+                    .all(|x| x.is_zero())
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_partial_sums_recursively_ragged() -> anyhow::Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        let max_degree = 2;
+        let numerators = vec![F::ONE; 5];
+        let denominators = [1, 2, 3, 4, 5]
+            .into_iter()
+            .map(|&i| F::from_canonical_u64(i))
+            .collect::<Vec<_>>();
+        let partials = partial_sums(&denominators, max_degree);
+
+        let nume_targets = numerators
+            .iter()
+            .map(|&n| builder.constant_extension(n.into()))
+            .collect::<Vec<_>>();
+        let deno_targets = denominators
+            .iter()
+            .map(|&d| builder.constant_extension(d.into()))
+            .collect::<Vec<_>>();
+        let partial_targets = partials
+            .iter()
+            .map(|_| builder.add_virtual_extension_target())
+            .collect::<Vec<_>>();
+        for (&target, &value) in partial_targets.iter().zip(&partials) {
+            pw.set_extension_target(target, value.into());
+        }
+        let acc = builder.zero_extension();
+
+        let constraints = check_partial_sums_recursively(
+            &mut builder,
+            &nume_targets,
+            &deno_targets,
+            &partial_targets,
+            acc,
+            max_degree,
+        );
+        assert_eq!(constraints.len(), partials.len());
+        for constraint in constraints {
+            builder.assert_zero_extension(constraint);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
     }
 }